@@ -1,4 +1,9 @@
+use anyhow::{Context, Result};
+use rusqlite::params;
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{epub, formats};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BookMeta {
@@ -6,48 +11,222 @@ pub struct BookMeta {
     pub title: String,
     pub author: String,
     pub file_path: String,
+    pub format: String,
     pub progress: f64,
     pub is_vectorized: bool,
+    /// Set when the source file is DRM-encrypted, so the frontend can mark
+    /// it non-readable/non-vectorizable instead of failing opaquely further
+    /// down the pipeline.
+    pub is_drm_protected: bool,
     pub added_at: i64,
     pub last_opened_at: Option<i64>,
 }
 
-/// Import an EPUB book into the library
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message")]
+pub enum ImportError {
+    Failed(String),
+}
+
+impl From<anyhow::Error> for ImportError {
+    fn from(e: anyhow::Error) -> Self {
+        ImportError::Failed(e.to_string())
+    }
+}
+
+/// A book row whose `file_path` no longer exists on disk.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrphanedBook {
+    pub id: String,
+    pub title: String,
+}
+
+/// Result of a library integrity scan. `orphaned` lists every book found
+/// with a missing `file_path` regardless of `delete_orphans` — it was only
+/// actually deleted if the scan was run with `delete_orphans: true`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LibraryScanReport {
+    pub scanned: u32,
+    pub orphaned: Vec<OrphanedBook>,
+}
+
+fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn row_to_book_meta(row: &rusqlite::Row) -> rusqlite::Result<BookMeta> {
+    Ok(BookMeta {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        author: row.get(2)?,
+        file_path: row.get(3)?,
+        format: row.get(4)?,
+        progress: row.get(5)?,
+        is_vectorized: row.get::<_, i64>(6)? != 0,
+        is_drm_protected: row.get::<_, i64>(7)? != 0,
+        added_at: row.get(8)?,
+        last_opened_at: row.get(9)?,
+    })
+}
+
+const BOOK_META_COLUMNS: &str = "id, title, author, file_path, format, progress, is_vectorized, \
+     is_drm_protected, added_at, last_opened_at";
+
+fn import_book_blocking(app: &AppHandle, file_path: &str) -> Result<BookMeta, ImportError> {
+    let format = formats::detect_format(file_path)?;
+    let is_drm_protected = format == "epub" && epub::detect_drm(file_path)?;
+
+    let metadata = formats::parser_for(format)?.parse_metadata(file_path)?;
+
+    let conn = crate::db::connection(app)?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let added_at = now_unix();
+    conn.execute(
+        "INSERT INTO books
+            (id, file_path, format, title, author, author_sort, publisher, language, description, subjects, added_at, is_drm_protected)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        params![
+            id,
+            file_path,
+            format,
+            metadata.title,
+            metadata.author,
+            metadata.author_sort,
+            metadata.publisher,
+            metadata.language,
+            metadata.description,
+            serde_json::to_string(&metadata.subjects).map_err(anyhow::Error::from)?,
+            added_at,
+            is_drm_protected,
+        ],
+    )
+    .map_err(anyhow::Error::from)?;
+
+    Ok(BookMeta {
+        id,
+        title: metadata.title,
+        author: metadata.author,
+        file_path: file_path.to_string(),
+        format: format.to_string(),
+        progress: 0.0,
+        is_vectorized: false,
+        is_drm_protected,
+        added_at,
+        last_opened_at: None,
+    })
+}
+
+/// Import a book into the library, detecting its format (EPUB, PDF, ...) and
+/// dispatching to the matching `BookParser`. DRM-protected EPUBs are still
+/// imported so they show up in the library, but flagged via
+/// `BookMeta::is_drm_protected` so the frontend can mark them non-readable/
+/// non-vectorizable instead of failing opaquely once chapter extraction hits
+/// encrypted content.
 #[tauri::command]
-pub async fn import_book(file_path: String) -> Result<BookMeta, String> {
-    // TODO: Parse EPUB, extract metadata, store in DB
-    let _ = file_path;
-    Err("Not implemented".into())
+pub async fn import_book(app: AppHandle, file_path: String) -> Result<BookMeta, ImportError> {
+    tauri::async_runtime::spawn_blocking(move || import_book_blocking(&app, &file_path))
+        .await
+        .map_err(|e| ImportError::Failed(e.to_string()))?
 }
 
 /// Get all books in the library
 #[tauri::command]
-pub async fn get_books() -> Result<Vec<BookMeta>, String> {
-    // TODO: Query database for all books
-    Ok(vec![])
+pub async fn get_books(app: AppHandle) -> Result<Vec<BookMeta>, String> {
+    tauri::async_runtime::spawn_blocking(move || -> Result<Vec<BookMeta>> {
+        let conn = crate::db::connection(&app)?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {BOOK_META_COLUMNS} FROM books ORDER BY author_sort, title"
+        ))?;
+        let rows = stmt.query_map([], row_to_book_meta)?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
 }
 
 /// Get a single book by ID
 #[tauri::command]
-pub async fn get_book(book_id: String) -> Result<BookMeta, String> {
-    let _ = book_id;
-    Err("Not implemented".into())
+pub async fn get_book(app: AppHandle, book_id: String) -> Result<BookMeta, String> {
+    tauri::async_runtime::spawn_blocking(move || -> Result<BookMeta> {
+        let conn = crate::db::connection(&app)?;
+        conn.query_row(
+            &format!("SELECT {BOOK_META_COLUMNS} FROM books WHERE id = ?1"),
+            params![book_id],
+            row_to_book_meta,
+        )
+        .context("book not found")
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
 }
 
 /// Delete a book from the library
 #[tauri::command]
-pub async fn delete_book(book_id: String) -> Result<(), String> {
-    let _ = book_id;
-    Err("Not implemented".into())
+pub async fn delete_book(app: AppHandle, book_id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || -> Result<()> {
+        let conn = crate::db::connection(&app)?;
+        conn.execute("DELETE FROM books WHERE id = ?1", params![book_id])?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// Scan the library for "ghost" books whose `file_path` no longer exists on
+/// disk, e.g. because the file was moved or deleted outside the app. When
+/// `delete_orphans` is set, matching rows are removed, cascading to their
+/// highlights/notes/bookmarks/chunks via the `ON DELETE CASCADE` foreign keys.
+#[tauri::command]
+pub async fn scan_library(app: AppHandle, delete_orphans: bool) -> Result<LibraryScanReport, String> {
+    tauri::async_runtime::spawn_blocking(move || -> Result<LibraryScanReport> {
+        let conn = crate::db::connection(&app)?;
+        let mut stmt = conn.prepare("SELECT id, title, file_path FROM books")?;
+        let rows: Vec<(String, String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        let scanned = rows.len() as u32;
+
+        let mut orphaned = Vec::new();
+        for (id, title, file_path) in rows {
+            if std::path::Path::new(&file_path).exists() {
+                continue;
+            }
+            if delete_orphans {
+                conn.execute("DELETE FROM books WHERE id = ?1", params![id])?;
+            }
+            orphaned.push(OrphanedBook { id, title });
+        }
+
+        Ok(LibraryScanReport { scanned, orphaned })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
 }
 
 /// Update reading progress for a book
 #[tauri::command]
 pub async fn update_book_progress(
+    app: AppHandle,
     book_id: String,
     progress: f64,
     cfi: String,
 ) -> Result<(), String> {
-    let _ = (book_id, progress, cfi);
-    Err("Not implemented".into())
+    tauri::async_runtime::spawn_blocking(move || -> Result<()> {
+        let conn = crate::db::connection(&app)?;
+        conn.execute(
+            "UPDATE books SET progress = ?1, current_cfi = ?2, last_opened_at = ?3 WHERE id = ?4",
+            params![progress, cfi, now_unix(), book_id],
+        )?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
 }
@@ -1,4 +1,16 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::formats;
+
+const CHUNK_TARGET_TOKENS: usize = 500;
+const CHUNK_OVERLAP_TOKENS: usize = 50;
+const LOCAL_EMBEDDING_DIMS: usize = 256;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -14,36 +26,558 @@ pub struct VectorizeStatus {
     pub total_chunks: u32,
     pub processed_chunks: u32,
     pub status: String,
+    /// Set when `status` is `"failed"`, describing why vectorization died.
+    pub error: Option<String>,
+}
+
+/// A chunk of chapter text, sized ahead of embedding.
+struct PendingChunk {
+    chapter_index: usize,
+    chapter_title: String,
+    content: String,
+    token_count: usize,
+}
+
+/// Split chapter text into overlapping chunks of roughly `target_tokens`
+/// whitespace-separated tokens, each with `overlap_tokens` of repeated
+/// context carried over from the previous chunk.
+fn chunk_text(text: &str, target_tokens: usize, overlap_tokens: usize) -> Vec<(String, usize)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return vec![];
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    loop {
+        let end = (start + target_tokens).min(words.len());
+        chunks.push((words[start..end].join(" "), end - start));
+        if end == words.len() {
+            break;
+        }
+        start = end - overlap_tokens;
+    }
+    chunks
+}
+
+/// Pluggable embedding backend: either a lightweight local hashing embedder
+/// (no network, works offline out of the box) or an HTTP endpoint configured
+/// via `READANY_EMBEDDING_ENDPOINT` for a real embedding model.
+trait EmbeddingBackend: Send + Sync {
+    /// Stable identifier for the backend + its configuration (e.g. which
+    /// endpoint it talks to). Stored alongside a book's chunks so a later
+    /// search can detect that the backend changed underneath it.
+    fn id(&self) -> String;
+    fn embed(&self, text: &str) -> Result<Vec<f32>>;
+}
+
+/// Deterministic bag-of-words hashing embedder. Not as good as a real model,
+/// but requires no extra downloads/services and keeps semantic search usable
+/// out of the box.
+struct LocalHashEmbedder {
+    dims: usize,
+}
+
+impl EmbeddingBackend for LocalHashEmbedder {
+    fn id(&self) -> String {
+        format!("local-hash:{}", self.dims)
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut vector = vec![0f32; self.dims];
+        for token in text.split_whitespace() {
+            let mut hasher = DefaultHasher::new();
+            token.to_lowercase().hash(&mut hasher);
+            let bucket = (hasher.finish() as usize) % self.dims;
+            vector[bucket] += 1.0;
+        }
+        normalize(&mut vector);
+        Ok(vector)
+    }
+}
+
+#[derive(Serialize)]
+struct EmbedRequest<'a> {
+    input: &'a str,
+}
+
+#[derive(Deserialize)]
+struct EmbedResponse {
+    embedding: Vec<f32>,
+}
+
+/// Calls out to a configured HTTP embedding endpoint, e.g. a local
+/// `text-embeddings-inference` server or a hosted embeddings API.
+struct HttpEmbedder {
+    endpoint: String,
+    client: reqwest::blocking::Client,
+}
+
+impl EmbeddingBackend for HttpEmbedder {
+    fn id(&self) -> String {
+        format!("http:{}", self.endpoint)
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let resp: EmbedResponse = self
+            .client
+            .post(&self.endpoint)
+            .json(&EmbedRequest { input: text })
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(resp.embedding)
+    }
+}
+
+fn embedding_backend() -> Box<dyn EmbeddingBackend> {
+    match std::env::var("READANY_EMBEDDING_ENDPOINT") {
+        Ok(endpoint) => Box::new(HttpEmbedder {
+            endpoint,
+            client: reqwest::blocking::Client::new(),
+        }),
+        Err(_) => Box::new(LocalHashEmbedder {
+            dims: LOCAL_EMBEDDING_DIMS,
+        }),
+    }
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in vector.iter_mut() {
+            *x /= norm;
+        }
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> Result<f64> {
+    if a.len() != b.len() {
+        anyhow::bail!(
+            "embedding dimension mismatch: query has {} dims, chunk has {}",
+            a.len(),
+            b.len()
+        );
+    }
+    if a.is_empty() {
+        return Ok(0.0);
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        Ok(0.0)
+    } else {
+        Ok((dot / (norm_a * norm_b)) as f64)
+    }
+}
+
+fn embedding_to_blob(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn blob_to_embedding(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn min_max_normalize(scores: &[f64]) -> Vec<f64> {
+    let min = scores.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    if !(max > min) {
+        return vec![1.0; scores.len()];
+    }
+    scores.iter().map(|s| (s - min) / (max - min)).collect()
+}
+
+/// BM25 ranking (k1 = 1.2, b = 0.75) over chunk content.
+fn bm25_scores(chunks: &[(String, String)], query: &str) -> Vec<f64> {
+    const K1: f64 = 1.2;
+    const B: f64 = 0.75;
+
+    let tokenize = |s: &str| {
+        s.to_lowercase()
+            .split_whitespace()
+            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_string())
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+    };
+
+    let docs: Vec<Vec<String>> = chunks.iter().map(|(_, content)| tokenize(content)).collect();
+    let query_terms = tokenize(query);
+    let n = docs.len() as f64;
+    let avg_len = if docs.is_empty() {
+        0.0
+    } else {
+        docs.iter().map(|d| d.len()).sum::<usize>() as f64 / n
+    };
+
+    let mut idf = std::collections::HashMap::new();
+    for term in &query_terms {
+        if idf.contains_key(term) {
+            continue;
+        }
+        let df = docs.iter().filter(|d| d.contains(term)).count() as f64;
+        idf.insert(term.clone(), ((n - df + 0.5) / (df + 0.5) + 1.0).ln());
+    }
+
+    docs.iter()
+        .map(|doc| {
+            let doc_len = doc.len() as f64;
+            query_terms
+                .iter()
+                .map(|term| {
+                    let tf = doc.iter().filter(|w| *w == term).count() as f64;
+                    if tf == 0.0 {
+                        return 0.0;
+                    }
+                    let idf = idf.get(term).copied().unwrap_or(0.0);
+                    idf * (tf * (K1 + 1.0))
+                        / (tf + K1 * (1.0 - B + B * doc_len / avg_len.max(1.0)))
+                })
+                .sum()
+        })
+        .collect()
+}
+
+fn book_source(conn: &Connection, book_id: &str) -> Result<(String, String)> {
+    conn.query_row(
+        "SELECT file_path, format FROM books WHERE id = ?1",
+        params![book_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .context("book not found")
+}
+
+fn set_vectorize_progress(conn: &Connection, book_id: &str, progress: f64, done: bool) -> Result<()> {
+    conn.execute(
+        "UPDATE books SET vectorize_progress = ?1, is_vectorized = ?2 WHERE id = ?3",
+        params![progress, done as i32, book_id],
+    )?;
+    Ok(())
+}
+
+/// Record that vectorization failed so `get_vectorize_status` can report
+/// `"failed"` instead of leaving the book stuck showing `"processing"`
+/// forever.
+fn set_vectorize_failed(conn: &Connection, book_id: &str, message: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE books SET is_vectorized = 0, vectorize_error = ?1 WHERE id = ?2",
+        params![message, book_id],
+    )?;
+    Ok(())
+}
+
+fn vectorize_book_blocking(app: &AppHandle, book_id: &str) -> Result<()> {
+    let conn = crate::db::connection(app)?;
+    if let Err(e) = vectorize_book_inner(&conn, book_id) {
+        set_vectorize_failed(&conn, book_id, &e.to_string())?;
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn vectorize_book_inner(conn: &Connection, book_id: &str) -> Result<()> {
+    let (file_path, format) = book_source(conn, book_id)?;
+    let chapters = formats::parser_for(&format)?.extract_chapters(&file_path)?;
+
+    let mut pending = Vec::new();
+    for chapter in &chapters {
+        for (content, token_count) in chunk_text(&chapter.text, CHUNK_TARGET_TOKENS, CHUNK_OVERLAP_TOKENS) {
+            pending.push(PendingChunk {
+                chapter_index: chapter.index,
+                chapter_title: chapter.title.clone(),
+                content,
+                token_count,
+            });
+        }
+    }
+
+    conn.execute("DELETE FROM chunks WHERE book_id = ?1", params![book_id])?;
+    conn.execute(
+        "UPDATE books SET vectorize_error = NULL WHERE id = ?1",
+        params![book_id],
+    )?;
+    set_vectorize_progress(conn, book_id, 0.0, false)?;
+
+    let total = pending.len().max(1);
+    let backend = embedding_backend();
+    let backend_id = backend.id();
+    let mut recorded_dims = false;
+    for (processed, chunk) in pending.into_iter().enumerate() {
+        let embedding = backend.embed(&chunk.content)?;
+        if !recorded_dims {
+            // Record which backend/dimensionality produced these vectors so
+            // a later search can tell if READANY_EMBEDDING_ENDPOINT has since
+            // changed underneath the stored embeddings.
+            conn.execute(
+                "UPDATE books SET embedding_backend = ?1, embedding_dims = ?2 WHERE id = ?3",
+                params![backend_id, embedding.len() as i64, book_id],
+            )?;
+            recorded_dims = true;
+        }
+        conn.execute(
+            "INSERT INTO chunks (id, book_id, chapter_index, chapter_title, content, token_count, embedding)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                uuid::Uuid::new_v4().to_string(),
+                book_id,
+                chunk.chapter_index as i64,
+                chunk.chapter_title,
+                chunk.content,
+                chunk.token_count as i64,
+                embedding_to_blob(&embedding),
+            ],
+        )?;
+        set_vectorize_progress(conn, book_id, (processed + 1) as f64 / total as f64, false)?;
+    }
+
+    set_vectorize_progress(conn, book_id, 1.0, true)?;
+    Ok(())
 }
 
 /// Start vectorization for a book
 #[tauri::command]
-pub async fn vectorize_book(book_id: String) -> Result<(), String> {
-    // TODO: Chunk book content, generate embeddings, store in DB
-    let _ = book_id;
-    Err("Not implemented".into())
+pub async fn vectorize_book(app: AppHandle, book_id: String) -> Result<(), String> {
+    // Vectorization can take a while, so it runs in the background; progress
+    // is polled through `get_vectorize_status` rather than awaited here.
+    tauri::async_runtime::spawn(async move {
+        let book_id_for_log = book_id.clone();
+        let result =
+            tauri::async_runtime::spawn_blocking(move || vectorize_book_blocking(&app, &book_id))
+                .await;
+        if let Ok(Err(e)) = result {
+            eprintln!("vectorize_book failed for {book_id_for_log}: {e:?}");
+        }
+    });
+    Ok(())
 }
 
 /// Search book content using RAG
 #[tauri::command]
 pub async fn search_book(
+    app: AppHandle,
     book_id: String,
     query: String,
     mode: String,
     top_k: u32,
 ) -> Result<Vec<SearchResult>, String> {
-    let _ = (book_id, query, mode, top_k);
-    Ok(vec![])
+    tauri::async_runtime::spawn_blocking(move || {
+        search_book_blocking(&app, &book_id, &query, &mode, top_k as usize)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+fn search_book_blocking(
+    app: &AppHandle,
+    book_id: &str,
+    query: &str,
+    mode: &str,
+    top_k: usize,
+) -> Result<Vec<SearchResult>> {
+    let conn = crate::db::connection(app)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, content, chapter_title, embedding FROM chunks WHERE book_id = ?1",
+    )?;
+    let rows = stmt.query_map(params![book_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<Vec<u8>>>(3)?,
+        ))
+    })?;
+    let chunks: Vec<(String, String, String, Option<Vec<u8>>)> =
+        rows.collect::<rusqlite::Result<_>>()?;
+
+    if chunks.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let semantic_scores = |query: &str| -> Result<Vec<f64>> {
+        let (stored_backend, stored_dims): (Option<String>, Option<i64>) = conn.query_row(
+            "SELECT embedding_backend, embedding_dims FROM books WHERE id = ?1",
+            params![book_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let (stored_backend, stored_dims) = stored_backend
+            .zip(stored_dims)
+            .context("book has not been vectorized yet")?;
+
+        let backend = embedding_backend();
+        if backend.id() != stored_backend {
+            anyhow::bail!(
+                "embedding backend changed since this book was vectorized (was `{stored_backend}`, now `{}`); re-vectorize to search semantically",
+                backend.id()
+            );
+        }
+
+        let query_embedding = backend.embed(query)?;
+        if query_embedding.len() as i64 != stored_dims {
+            anyhow::bail!(
+                "query embedding has {} dims but stored chunks have {stored_dims}; re-vectorize to search semantically",
+                query_embedding.len()
+            );
+        }
+
+        chunks
+            .iter()
+            .map(|(_, _, _, blob)| match blob {
+                Some(blob) => cosine_similarity(&query_embedding, &blob_to_embedding(blob)),
+                None => Ok(0.0),
+            })
+            .collect()
+    };
+
+    let keyword_scores = || -> Vec<f64> {
+        let docs: Vec<(String, String)> = chunks
+            .iter()
+            .map(|(id, content, _, _)| (id.clone(), content.clone()))
+            .collect();
+        bm25_scores(&docs, query)
+    };
+
+    let scores: Vec<f64> = match mode {
+        "semantic" => semantic_scores(query)?,
+        "keyword" => keyword_scores(),
+        "hybrid" => {
+            let semantic = min_max_normalize(&semantic_scores(query)?);
+            let keyword = min_max_normalize(&keyword_scores());
+            semantic
+                .iter()
+                .zip(keyword.iter())
+                .map(|(s, k)| 0.5 * s + 0.5 * k)
+                .collect()
+        }
+        other => anyhow::bail!("unknown search mode: {other}"),
+    };
+
+    let mut results: Vec<SearchResult> = chunks
+        .into_iter()
+        .zip(scores)
+        .map(|((id, content, chapter_title, _), score)| SearchResult {
+            chunk_id: id,
+            content,
+            score,
+            chapter_title,
+        })
+        .collect();
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(top_k);
+    Ok(results)
 }
 
 /// Get vectorization status for a book
 #[tauri::command]
-pub async fn get_vectorize_status(book_id: String) -> Result<VectorizeStatus, String> {
-    let _ = &book_id;
-    Ok(VectorizeStatus {
-        book_id,
-        total_chunks: 0,
-        processed_chunks: 0,
-        status: "idle".into(),
+pub async fn get_vectorize_status(app: AppHandle, book_id: String) -> Result<VectorizeStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || -> Result<VectorizeStatus> {
+        let conn = crate::db::connection(&app)?;
+        let (progress, is_vectorized, vectorize_error): (f64, bool, Option<String>) = conn
+            .query_row(
+                "SELECT vectorize_progress, is_vectorized, vectorize_error FROM books WHERE id = ?1",
+                params![book_id],
+                |row| Ok((row.get(0)?, row.get::<_, i64>(1)? != 0, row.get(2)?)),
+            )?;
+        // Chunks are inserted as they're embedded, so the row count *is* the
+        // processed count; the target total is derived from how far along
+        // `vectorize_progress` says we are.
+        let processed_chunks: u32 = conn.query_row(
+            "SELECT COUNT(*) FROM chunks WHERE book_id = ?1",
+            params![book_id],
+            |row| row.get(0),
+        )?;
+        let total_chunks = if progress > 0.0 {
+            (processed_chunks as f64 / progress).round() as u32
+        } else {
+            0
+        };
+        let status = if vectorize_error.is_some() {
+            "failed"
+        } else if is_vectorized {
+            "completed"
+        } else if progress > 0.0 {
+            "processing"
+        } else {
+            "idle"
+        };
+        Ok(VectorizeStatus {
+            book_id,
+            total_chunks,
+            processed_chunks,
+            status: status.to_string(),
+            error: vectorize_error,
+        })
     })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_text_overlaps_consecutive_chunks() {
+        let words: Vec<String> = (1..=25).map(|n| n.to_string()).collect();
+        let text = words.join(" ");
+        let chunks = chunk_text(&text, 10, 3);
+
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[0].0, "1 2 3 4 5 6 7 8 9 10");
+        // Second chunk starts 3 tokens back into the first chunk's tail.
+        assert_eq!(chunks[1].0, "8 9 10 11 12 13 14 15 16 17");
+        assert_eq!(chunks.last().unwrap().0, "22 23 24 25");
+    }
+
+    #[test]
+    fn chunk_text_empty_input_yields_no_chunks() {
+        assert!(chunk_text("   ", 10, 3).is_empty());
+    }
+
+    #[test]
+    fn bm25_scores_ranks_matching_doc_above_unrelated_doc() {
+        let docs = vec![
+            ("a".to_string(), "the quick brown fox jumps".to_string()),
+            ("b".to_string(), "an entirely unrelated sentence".to_string()),
+        ];
+        let scores = bm25_scores(&docs, "fox");
+        assert!(scores[0] > scores[1]);
+    }
+
+    #[test]
+    fn bm25_scores_zero_for_no_query_terms() {
+        let docs = vec![("a".to_string(), "some content".to_string())];
+        let scores = bm25_scores(&docs, "");
+        assert_eq!(scores, vec![0.0]);
+    }
+
+    #[test]
+    fn cosine_similarity_identical_vectors_is_one() {
+        let v = vec![1.0, 2.0, 3.0];
+        let sim = cosine_similarity(&v, &v).unwrap();
+        assert!((sim - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cosine_similarity_mismatched_dims_errors() {
+        let a = vec![1.0, 0.0];
+        let b = vec![1.0, 0.0, 0.0];
+        assert!(cosine_similarity(&a, &b).is_err());
+    }
+
+    #[test]
+    fn min_max_normalize_maps_range_to_zero_one() {
+        let scores = vec![1.0, 2.0, 3.0];
+        assert_eq!(min_max_normalize(&scores), vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn min_max_normalize_constant_input_yields_ones() {
+        let scores = vec![5.0, 5.0, 5.0];
+        assert_eq!(min_max_normalize(&scores), vec![1.0, 1.0, 1.0]);
+    }
 }
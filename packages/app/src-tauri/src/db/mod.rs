@@ -1,8 +1,16 @@
 pub mod schema;
 
 use anyhow::Result;
+use rusqlite::Connection;
 use tauri::{AppHandle, Manager};
 
+fn db_path(app: &AppHandle) -> std::path::PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("failed to get app data dir")
+        .join("readany.db")
+}
+
 /// Initialize the SQLite database synchronously (called in setup before frontend loads)
 pub fn init_database_sync(app: &AppHandle) -> Result<()> {
     let app_dir = app
@@ -11,8 +19,20 @@ pub fn init_database_sync(app: &AppHandle) -> Result<()> {
         .expect("failed to get app data dir");
     std::fs::create_dir_all(&app_dir)?;
 
-    let db_path = app_dir.join("readany.db");
-    schema::initialize(&db_path)?;
+    schema::initialize(&db_path(app))?;
 
     Ok(())
 }
+
+/// Open a connection to the app's SQLite database. Commands that need to
+/// read or write `books`/`chunks`/etc. from Rust go through this rather than
+/// the frontend's SQL plugin, since binary embedding data is awkward to move
+/// across the JS bridge.
+pub fn connection(app: &AppHandle) -> Result<Connection> {
+    let conn = Connection::open(db_path(app))?;
+    // Required for the schema's `ON DELETE CASCADE` foreign keys (relied on
+    // by e.g. `scan_library`'s orphan cleanup) to actually cascade; SQLite
+    // has this off by default per connection.
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    Ok(conn)
+}
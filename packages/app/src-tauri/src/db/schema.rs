@@ -1,13 +1,42 @@
 use anyhow::Result;
-use rusqlite::Connection;
+use rusqlite::{Connection, Transaction};
 use std::path::Path;
 
-/// Initialize SQLite database with schema
-/// IMPORTANT: This schema must stay in sync with the frontend's database.ts
-pub fn initialize(db_path: &Path) -> Result<()> {
-    let conn = Connection::open(db_path)?;
+/// One schema change, applied inside a transaction and recorded by bumping
+/// `PRAGMA user_version` to `version` on success.
+struct Migration {
+    version: i64,
+    name: &'static str,
+    run: fn(&Transaction) -> rusqlite::Result<()>,
+}
+
+/// Ordered, append-only list of migrations. Never edit a migration once
+/// released — add a new one instead, even to fix a mistake in an earlier one.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial schema",
+        run: migration_001_initial,
+    },
+    Migration {
+        version: 2,
+        name: "add is_drm_protected flag to books",
+        run: migration_002_drm_flag,
+    },
+    Migration {
+        version: 3,
+        name: "record embedding backend/dims on books",
+        run: migration_003_embedding_metadata,
+    },
+    Migration {
+        version: 4,
+        name: "add vectorize_error to books",
+        run: migration_004_vectorize_error,
+    },
+];
 
-    conn.execute_batch(
+fn migration_001_initial(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
         "
         CREATE TABLE IF NOT EXISTS books (
             id TEXT PRIMARY KEY,
@@ -15,6 +44,7 @@ pub fn initialize(db_path: &Path) -> Result<()> {
             format TEXT NOT NULL DEFAULT 'epub',
             title TEXT NOT NULL DEFAULT '',
             author TEXT NOT NULL DEFAULT '',
+            author_sort TEXT NOT NULL DEFAULT '',
             publisher TEXT,
             language TEXT,
             isbn TEXT,
@@ -128,13 +158,85 @@ pub fn initialize(db_path: &Path) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_reading_sessions_book ON reading_sessions(book_id);
         CREATE INDEX IF NOT EXISTS idx_chunks_book ON chunks(book_id);
         ",
-    )?;
+    )
+}
 
-    // Migrations for existing databases
-    // Add format column if missing (from older schema)
-    let _ = conn.execute_batch("ALTER TABLE books ADD COLUMN format TEXT NOT NULL DEFAULT 'epub'");
-    // Add tags column if missing
-    let _ = conn.execute_batch("ALTER TABLE books ADD COLUMN tags TEXT DEFAULT '[]'");
+fn migration_002_drm_flag(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        "ALTER TABLE books ADD COLUMN is_drm_protected INTEGER NOT NULL DEFAULT 0;",
+    )
+}
+
+fn migration_003_embedding_metadata(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        "
+        ALTER TABLE books ADD COLUMN embedding_backend TEXT;
+        ALTER TABLE books ADD COLUMN embedding_dims INTEGER;
+        ",
+    )
+}
+
+fn migration_004_vectorize_error(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch("ALTER TABLE books ADD COLUMN vectorize_error TEXT;")
+}
+
+/// Initialize the SQLite database, bringing it from whatever `user_version`
+/// it's currently at up to the latest migration.
+///
+/// IMPORTANT: This schema must stay in sync with the frontend's database.ts
+pub fn initialize(db_path: &Path) -> Result<()> {
+    let mut conn = Connection::open(db_path)?;
+    let current_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    for migration in MIGRATIONS {
+        if migration.version <= current_version {
+            continue;
+        }
+
+        let tx = conn.transaction()?;
+        (migration.run)(&tx)?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+        println!(
+            "applied migration {}: {}",
+            migration.version, migration.name
+        );
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrations_are_sorted_and_gapless_from_one() {
+        let versions: Vec<i64> = MIGRATIONS.iter().map(|m| m.version).collect();
+        let expected: Vec<i64> = (1..=MIGRATIONS.len() as i64).collect();
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn initialize_applies_all_migrations_and_is_idempotent() {
+        let dir = std::env::temp_dir().join(format!(
+            "readany-schema-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("test.sqlite");
+        let _ = std::fs::remove_file(&db_path);
+
+        initialize(&db_path).unwrap();
+        // Re-running against an already-migrated database must be a no-op,
+        // not re-apply (and fail on) earlier ALTER TABLE statements.
+        initialize(&db_path).unwrap();
+
+        let conn = Connection::open(&db_path).unwrap();
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.last().unwrap().version);
+
+        std::fs::remove_file(&db_path).ok();
+        std::fs::remove_dir(&dir).ok();
+    }
+}
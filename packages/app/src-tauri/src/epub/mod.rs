@@ -1,39 +1,706 @@
 /// EPUB parsing module
 
+use std::collections::HashMap;
+use std::io::Read;
+
+use anyhow::{anyhow, Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EpubChapter {
     pub index: usize,
     pub title: String,
-    pub content: String, // HTML content
+    pub content: String, // original HTML content, preserved so the renderer keeps formatting
+    /// Cleaned plain text (markup stripped, block boundaries turned into
+    /// newlines) used for chunking/embedding downstream.
+    pub text: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EpubMetadata {
     pub title: String,
     pub author: String,
+    /// Sort-friendly form of `author` (e.g. "Tolkien, J. R. R."), used for
+    /// alphabetical library ordering. Falls back to `author` when the OPF
+    /// doesn't carry `file-as`/`opf:file-as` information.
+    pub author_sort: String,
     pub publisher: Option<String>,
     pub language: Option<String>,
     pub description: Option<String>,
     pub subjects: Vec<String>,
 }
 
+/// A `<dc:creator>` entry plus whatever role/sort-name info we could find for it.
+#[derive(Default, Debug)]
+struct Creator {
+    id: Option<String>,
+    name: String,
+    role: Option<String>,
+    file_as: Option<String>,
+}
+
+/// Algorithm URIs used purely to obfuscate embedded fonts (IDPF/Adobe
+/// font-mangling), as opposed to actually protecting the book with DRM.
+/// Plenty of perfectly legal, non-DRM EPUBs embed custom fonts this way.
+const FONT_OBFUSCATION_ALGORITHMS: &[&str] = &[
+    "http://www.idpf.org/2008/embedding",
+    "http://ns.adobe.com/pdf/enc#RC",
+];
+
+fn is_font_resource(uri: &str) -> bool {
+    let lower = uri.to_lowercase();
+    [".ttf", ".otf", ".woff", ".woff2"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+}
+
+/// One `<enc:EncryptedData>` entry from `META-INF/encryption.xml`.
+struct EncryptedResource {
+    uri: String,
+    algorithm: String,
+}
+
+impl EncryptedResource {
+    /// An encrypted resource only counts as DRM if it isn't just a font
+    /// being obfuscated with one of the known (non-DRM) font algorithms.
+    fn is_drm(&self) -> bool {
+        !(is_font_resource(&self.uri) && FONT_OBFUSCATION_ALGORITHMS.contains(&self.algorithm.as_str()))
+    }
+}
+
+/// Parse `META-INF/encryption.xml`'s `<enc:EncryptedData>` entries, pairing
+/// each `EncryptionMethod`'s `Algorithm` with the `CipherReference` URI it encrypts.
+fn parse_encryption_xml(xml: &str) -> Result<Vec<EncryptedResource>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut resources = Vec::new();
+    let mut algorithm: Option<String> = None;
+    let mut uri: Option<String> = None;
+
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"EncryptedData" => {
+                    algorithm = None;
+                    uri = None;
+                }
+                b"EncryptionMethod" => {
+                    for attr in e.attributes().flatten() {
+                        if attr.key.local_name().as_ref() == b"Algorithm" {
+                            algorithm = Some(attr.unescape_value()?.into_owned());
+                        }
+                    }
+                }
+                b"CipherReference" => {
+                    for attr in e.attributes().flatten() {
+                        if attr.key.local_name().as_ref() == b"URI" {
+                            uri = Some(attr.unescape_value()?.into_owned());
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::End(e) if e.local_name().as_ref() == b"EncryptedData" => {
+                if let (Some(algorithm), Some(uri)) = (algorithm.take(), uri.take()) {
+                    resources.push(EncryptedResource { uri, algorithm });
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(resources)
+}
+
+/// Check whether an EPUB container declares DRM-encrypted content. A
+/// `META-INF/encryption.xml` entry only counts as DRM once it's encrypting
+/// something other than a font with a known font-obfuscation algorithm;
+/// an Adobe Adept rights file is always an unambiguous DRM signal.
+pub fn detect_drm(file_path: &str) -> Result<bool> {
+    let file = std::fs::File::open(file_path)
+        .with_context(|| format!("failed to open EPUB at {file_path}"))?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    if archive.by_name("META-INF/rights.xml").is_ok() {
+        return Ok(true);
+    }
+
+    let mut encryption_xml = String::new();
+    match archive.by_name("META-INF/encryption.xml") {
+        Ok(mut entry) => entry.read_to_string(&mut encryption_xml)?,
+        Err(_) => return Ok(false),
+    };
+
+    Ok(parse_encryption_xml(&encryption_xml)?
+        .iter()
+        .any(EncryptedResource::is_drm))
+}
+
+/// Locate the OPF package document path via `META-INF/container.xml`.
+fn find_opf_path(archive: &mut zip::ZipArchive<std::fs::File>) -> Result<String> {
+    let mut container = String::new();
+    archive
+        .by_name("META-INF/container.xml")
+        .context("EPUB is missing META-INF/container.xml")?
+        .read_to_string(&mut container)?;
+
+    let mut reader = Reader::from_str(&container);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Empty(e) | Event::Start(e) if e.local_name().as_ref() == b"rootfile" => {
+                for attr in e.attributes().flatten() {
+                    if attr.key.local_name().as_ref() == b"full-path" {
+                        return Ok(attr.unescape_value()?.into_owned());
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Err(anyhow!("could not find rootfile in container.xml"))
+}
+
 /// Parse an EPUB file and extract metadata
-pub fn parse_metadata(_file_path: &str) -> anyhow::Result<EpubMetadata> {
-    // TODO: Use epub crate to parse
+pub fn parse_metadata(file_path: &str) -> Result<EpubMetadata> {
+    let file = std::fs::File::open(file_path)
+        .with_context(|| format!("failed to open EPUB at {file_path}"))?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    let opf_path = find_opf_path(&mut archive)?;
+    let mut opf = String::new();
+    archive
+        .by_name(&opf_path)
+        .with_context(|| format!("OPF package document not found at {opf_path}"))?
+        .read_to_string(&mut opf)?;
+
+    parse_opf_metadata(&opf)
+}
+
+/// Parse book metadata out of an OPF package document's raw XML, handling
+/// both EPUB2 (`opf:role`/`opf:file-as` attributes on `<dc:creator>`) and
+/// EPUB3 (`<meta refines="#id" property="role|file-as">`) creator metadata.
+fn parse_opf_metadata(opf: &str) -> Result<EpubMetadata> {
+    let mut reader = Reader::from_str(opf);
+    reader.config_mut().trim_text(true);
+
+    let mut is_epub3 = false;
+    let mut title = String::new();
+    let mut publisher = None;
+    let mut language = None;
+    let mut description = None;
+    let mut subjects = Vec::new();
+
+    let mut creators: Vec<Creator> = Vec::new();
+    // EPUB3 `<meta refines="#id" property="role|file-as">value</meta>`, keyed by the id it refines.
+    let mut refine_role: HashMap<String, String> = HashMap::new();
+    let mut refine_file_as: HashMap<String, String> = HashMap::new();
+
+    #[derive(PartialEq)]
+    enum Capture {
+        None,
+        Title,
+        Publisher,
+        Language,
+        Description,
+        Subject,
+        Creator { id: Option<String>, role: Option<String>, file_as: Option<String> },
+        RefineMeta { refines: String, property: String },
+    }
+    let mut capture = Capture::None;
+    let mut text = String::new();
+
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => {
+                let name = e.local_name();
+                match name.as_ref() {
+                    b"package" => {
+                        for attr in e.attributes().flatten() {
+                            if attr.key.local_name().as_ref() == b"version" {
+                                let version = attr.unescape_value()?;
+                                is_epub3 = version.starts_with('3');
+                            }
+                        }
+                    }
+                    b"title" => {
+                        capture = Capture::Title;
+                        text.clear();
+                    }
+                    b"publisher" => {
+                        capture = Capture::Publisher;
+                        text.clear();
+                    }
+                    b"language" => {
+                        capture = Capture::Language;
+                        text.clear();
+                    }
+                    b"description" => {
+                        capture = Capture::Description;
+                        text.clear();
+                    }
+                    b"subject" => {
+                        capture = Capture::Subject;
+                        text.clear();
+                    }
+                    b"creator" => {
+                        let mut id = None;
+                        let mut role = None;
+                        let mut file_as = None;
+                        for attr in e.attributes().flatten() {
+                            match attr.key.local_name().as_ref() {
+                                b"id" => id = Some(attr.unescape_value()?.into_owned()),
+                                b"role" => role = Some(attr.unescape_value()?.into_owned()),
+                                b"file-as" => file_as = Some(attr.unescape_value()?.into_owned()),
+                                _ => {}
+                            }
+                        }
+                        capture = Capture::Creator { id, role, file_as };
+                        text.clear();
+                    }
+                    b"meta" => {
+                        let mut refines = None;
+                        let mut property = None;
+                        for attr in e.attributes().flatten() {
+                            match attr.key.local_name().as_ref() {
+                                b"refines" => refines = Some(attr.unescape_value()?.into_owned()),
+                                b"property" => property = Some(attr.unescape_value()?.into_owned()),
+                                _ => {}
+                            }
+                        }
+                        if let (Some(refines), Some(property)) = (refines, property) {
+                            let refines = refines.trim_start_matches('#').to_string();
+                            if property == "role" || property == "file-as" {
+                                capture = Capture::RefineMeta { refines, property };
+                                text.clear();
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(e) => {
+                text.push_str(&e.unescape()?.into_owned());
+            }
+            Event::CData(e) => {
+                text.push_str(&String::from_utf8_lossy(&e.into_inner()));
+            }
+            Event::End(e) => {
+                let name = e.local_name();
+                match name.as_ref() {
+                    b"title" if capture == Capture::Title => title = text.trim().to_string(),
+                    b"publisher" if capture == Capture::Publisher => {
+                        publisher = Some(text.trim().to_string())
+                    }
+                    b"language" if capture == Capture::Language => {
+                        language = Some(text.trim().to_string())
+                    }
+                    b"description" if capture == Capture::Description => {
+                        description = Some(text.trim().to_string())
+                    }
+                    b"subject" if capture == Capture::Subject => {
+                        subjects.push(text.trim().to_string())
+                    }
+                    b"creator" => {
+                        if let Capture::Creator { id, role, file_as } =
+                            std::mem::replace(&mut capture, Capture::None)
+                        {
+                            creators.push(Creator {
+                                id,
+                                name: text.trim().to_string(),
+                                role,
+                                file_as,
+                            });
+                        }
+                    }
+                    b"meta" => {
+                        if let Capture::RefineMeta { refines, property } =
+                            std::mem::replace(&mut capture, Capture::None)
+                        {
+                            let value = text.trim().to_string();
+                            if property == "role" {
+                                refine_role.insert(refines, value);
+                            } else {
+                                refine_file_as.insert(refines, value);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                if !matches!(capture, Capture::Creator { .. } | Capture::RefineMeta { .. }) {
+                    capture = Capture::None;
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    // For EPUB3, roles/sort-names live in refines-meta keyed by the creator's id.
+    if is_epub3 {
+        for creator in &mut creators {
+            if let Some(id) = &creator.id {
+                if creator.role.is_none() {
+                    creator.role = refine_role.get(id).cloned();
+                }
+                if creator.file_as.is_none() {
+                    creator.file_as = refine_file_as.get(id).cloned();
+                }
+            }
+        }
+    }
+
+    // Only creators explicitly marked as authors count, unless none of them
+    // carry role information at all (common in hand-rolled EPUB2 files), in
+    // which case every `<dc:creator>` is assumed to be an author.
+    let any_role_present = creators.iter().any(|c| c.role.is_some());
+    let authors: Vec<&Creator> = creators
+        .iter()
+        .filter(|c| !any_role_present || c.role.as_deref() == Some("aut"))
+        .collect();
+
+    let author = authors
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" & ");
+    let author_sort = authors
+        .iter()
+        .map(|c| c.file_as.as_deref().unwrap_or(&c.name))
+        .collect::<Vec<_>>()
+        .join(" & ");
+
     Ok(EpubMetadata {
-        title: String::new(),
-        author: String::new(),
-        publisher: None,
-        language: None,
-        description: None,
-        subjects: vec![],
+        title,
+        author,
+        author_sort,
+        publisher,
+        language,
+        description,
+        subjects,
     })
 }
 
+/// Elements whose text content must never end up in the extracted chapter text.
+const SUPPRESSED_TAGS: &[&[u8]] = &[b"style", b"script", b"nav", b"iframe", b"svg"];
+
+/// Block-level elements: a newline is inserted at their boundaries so
+/// paragraphs/list items/etc. don't run together in the plain-text output.
+const BLOCK_TAGS: &[&[u8]] = &[
+    b"p", b"div", b"br", b"li", b"ul", b"ol", b"blockquote", b"section", b"article", b"header",
+    b"footer", b"table", b"tr", b"pre", b"hr", b"figure", b"figcaption",
+];
+
+fn is_heading(name: &[u8]) -> bool {
+    matches!(name, b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6")
+}
+
+/// Resolve an OPF-relative href against the OPF file's own directory,
+/// collapsing `.` and `..` segments (EPUB zip paths always use `/`).
+fn resolve_href(opf_dir: &str, href: &str) -> String {
+    let mut parts: Vec<&str> = if opf_dir.is_empty() {
+        Vec::new()
+    } else {
+        opf_dir.split('/').collect()
+    };
+    for segment in href.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            other => parts.push(other),
+        }
+    }
+    parts.join("/")
+}
+
+/// Parse the OPF manifest + spine into the ordered list of content-document
+/// paths (relative to the zip root) that make up the reading order.
+fn parse_spine_hrefs(opf: &str, opf_dir: &str) -> Result<Vec<String>> {
+    let mut reader = Reader::from_str(opf);
+    reader.config_mut().trim_text(true);
+
+    let mut manifest: HashMap<String, String> = HashMap::new();
+    let mut spine: Vec<String> = Vec::new();
+
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"item" => {
+                    let mut id = None;
+                    let mut href = None;
+                    for attr in e.attributes().flatten() {
+                        match attr.key.local_name().as_ref() {
+                            b"id" => id = Some(attr.unescape_value()?.into_owned()),
+                            b"href" => href = Some(attr.unescape_value()?.into_owned()),
+                            _ => {}
+                        }
+                    }
+                    if let (Some(id), Some(href)) = (id, href) {
+                        manifest.insert(id, href);
+                    }
+                }
+                b"itemref" => {
+                    for attr in e.attributes().flatten() {
+                        if attr.key.local_name().as_ref() == b"idref" {
+                            spine.push(attr.unescape_value()?.into_owned());
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(spine
+        .into_iter()
+        .filter_map(|idref| manifest.get(&idref))
+        .map(|href| resolve_href(opf_dir, href))
+        .collect())
+}
+
+/// Strip markup from an XHTML content document, returning cleaned plain text
+/// plus any text captured from `<h1>`-`<h6>` elements (used as the chapter title).
+fn strip_markup(xhtml: &str) -> Result<(String, String)> {
+    let mut reader = Reader::from_str(xhtml);
+    reader.config_mut().trim_text(false);
+
+    let mut text = String::new();
+    let mut title = String::new();
+    let mut suppress_stack: Vec<&'static [u8]> = Vec::new();
+    let mut heading_depth = 0usize;
+
+    let resolve_entity = |entity: &str| match entity {
+        "nbsp" => Some("\u{A0}"),
+        _ => None,
+    };
+
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => {
+                let name = e.local_name();
+                if let Some(tag) = SUPPRESSED_TAGS.iter().find(|t| **t == name.as_ref()) {
+                    suppress_stack.push(tag);
+                } else if is_heading(name.as_ref()) {
+                    heading_depth += 1;
+                } else if BLOCK_TAGS.contains(&name.as_ref()) && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            // Void elements like `<br/>`/`<hr/>` self-close: quick-xml reports
+            // them as a single Empty event rather than a Start/End pair, so
+            // the block-boundary newline has to be applied here too (there's
+            // no matching End, so suppressed tags can't meaningfully self-close).
+            Event::Empty(e) => {
+                let name = e.local_name();
+                if BLOCK_TAGS.contains(&name.as_ref()) && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            Event::End(e) => {
+                let name = e.local_name();
+                if suppress_stack.last().is_some_and(|t| *t == name.as_ref()) {
+                    suppress_stack.pop();
+                } else if is_heading(name.as_ref()) {
+                    heading_depth = heading_depth.saturating_sub(1);
+                } else if BLOCK_TAGS.contains(&name.as_ref()) && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            Event::Text(e) => {
+                if suppress_stack.is_empty() {
+                    let chunk = e.unescape_with(resolve_entity)?;
+                    text.push_str(&chunk);
+                    if heading_depth > 0 {
+                        title.push_str(&chunk);
+                    }
+                }
+            }
+            Event::CData(e) => {
+                if suppress_stack.is_empty() {
+                    let chunk = String::from_utf8_lossy(&e.into_inner()).into_owned();
+                    text.push_str(&chunk);
+                    if heading_depth > 0 {
+                        title.push_str(&chunk);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    // Collapse the newlines/whitespace introduced by block-boundary markers.
+    let cleaned = text
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut cleaned = cleaned.trim().to_string();
+    while cleaned.contains("\n\n\n") {
+        cleaned = cleaned.replace("\n\n\n", "\n\n");
+    }
+
+    Ok((cleaned, title.trim().to_string()))
+}
+
 /// Extract all chapters from an EPUB file
-pub fn extract_chapters(_file_path: &str) -> anyhow::Result<Vec<EpubChapter>> {
-    // TODO: Use epub crate to extract chapters
-    Ok(vec![])
+pub fn extract_chapters(file_path: &str) -> Result<Vec<EpubChapter>> {
+    let file = std::fs::File::open(file_path)
+        .with_context(|| format!("failed to open EPUB at {file_path}"))?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    let opf_path = find_opf_path(&mut archive)?;
+    let opf_dir = opf_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+    let mut opf = String::new();
+    archive
+        .by_name(&opf_path)
+        .with_context(|| format!("OPF package document not found at {opf_path}"))?
+        .read_to_string(&mut opf)?;
+
+    let hrefs = parse_spine_hrefs(&opf, opf_dir)?;
+
+    let mut chapters = Vec::with_capacity(hrefs.len());
+    for (index, href) in hrefs.into_iter().enumerate() {
+        let mut content = String::new();
+        archive
+            .by_name(&href)
+            .with_context(|| format!("spine item not found in EPUB: {href}"))?
+            .read_to_string(&mut content)?;
+
+        let (text, heading_title) = strip_markup(&content)?;
+        let title = if heading_title.is_empty() {
+            format!("Chapter {}", index + 1)
+        } else {
+            heading_title
+        };
+
+        chapters.push(EpubChapter {
+            index,
+            title,
+            content,
+            text,
+        });
+    }
+
+    Ok(chapters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_href_collapses_dot_dot_segments() {
+        assert_eq!(resolve_href("OEBPS/text", "../images/cover.jpg"), "OEBPS/images/cover.jpg");
+        assert_eq!(resolve_href("OEBPS", "chapter1.xhtml"), "OEBPS/chapter1.xhtml");
+        assert_eq!(resolve_href("", "chapter1.xhtml"), "chapter1.xhtml");
+    }
+
+    #[test]
+    fn strip_markup_resolves_nbsp_entity() {
+        let (text, _) = strip_markup("<p>a&nbsp;b</p>").unwrap();
+        assert_eq!(text, "a\u{A0}b");
+    }
+
+    #[test]
+    fn strip_markup_captures_heading_as_title() {
+        let (text, title) = strip_markup("<h1>Chapter One</h1><p>Body text.</p>").unwrap();
+        assert_eq!(title, "Chapter One");
+        assert!(text.contains("Body text."));
+    }
+
+    #[test]
+    fn strip_markup_drops_suppressed_tag_content() {
+        let (text, _) = strip_markup("<p>Visible.</p><script>hidden();</script>").unwrap();
+        assert!(text.contains("Visible."));
+        assert!(!text.contains("hidden"));
+    }
+
+    #[test]
+    fn strip_markup_inserts_newline_for_self_closing_br() {
+        let (text, _) = strip_markup("<p>line one<br/>line two</p>").unwrap();
+        assert_eq!(text, "line one\nline two");
+    }
+
+    #[test]
+    fn is_font_resource_matches_known_font_extensions() {
+        assert!(is_font_resource("Fonts/Embedded.ttf"));
+        assert!(is_font_resource("Fonts/Embedded.WOFF2"));
+        assert!(!is_font_resource("Text/chapter1.xhtml"));
+    }
+
+    #[test]
+    fn parse_opf_metadata_resolves_epub2_role_and_file_as() {
+        let opf = r#"<package version="2.0">
+            <metadata xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:opf="http://www.idpf.org/2007/opf">
+                <dc:title>Book</dc:title>
+                <dc:creator opf:role="aut" opf:file-as="Doe, Jane">Jane Doe</dc:creator>
+                <dc:creator opf:role="edt" opf:file-as="Smith, Bob">Bob Smith</dc:creator>
+            </metadata>
+        </package>"#;
+        let meta = parse_opf_metadata(opf).unwrap();
+        assert_eq!(meta.author, "Jane Doe");
+        assert_eq!(meta.author_sort, "Doe, Jane");
+    }
+
+    #[test]
+    fn parse_opf_metadata_resolves_epub3_refines_role_and_file_as() {
+        let opf = r#"<package version="3.0">
+            <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+                <dc:title>Book</dc:title>
+                <dc:creator id="creator1">Jane Doe</dc:creator>
+                <meta refines="#creator1" property="role">aut</meta>
+                <meta refines="#creator1" property="file-as">Doe, Jane</meta>
+            </metadata>
+        </package>"#;
+        let meta = parse_opf_metadata(opf).unwrap();
+        assert_eq!(meta.author, "Jane Doe");
+        assert_eq!(meta.author_sort, "Doe, Jane");
+    }
+
+    #[test]
+    fn parse_opf_metadata_joins_multiple_authors_with_ampersand() {
+        let opf = r#"<package version="2.0">
+            <metadata xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:opf="http://www.idpf.org/2007/opf">
+                <dc:title>Book</dc:title>
+                <dc:creator opf:role="aut">Jane Doe</dc:creator>
+                <dc:creator opf:role="aut">John Smith</dc:creator>
+            </metadata>
+        </package>"#;
+        let meta = parse_opf_metadata(opf).unwrap();
+        assert_eq!(meta.author, "Jane Doe & John Smith");
+    }
+
+    #[test]
+    fn parse_opf_metadata_treats_all_creators_as_authors_when_no_role_present() {
+        let opf = r#"<package version="2.0">
+            <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+                <dc:title>Book</dc:title>
+                <dc:creator>Jane Doe</dc:creator>
+                <dc:creator>John Smith</dc:creator>
+            </metadata>
+        </package>"#;
+        let meta = parse_opf_metadata(opf).unwrap();
+        assert_eq!(meta.author, "Jane Doe & John Smith");
+    }
 }
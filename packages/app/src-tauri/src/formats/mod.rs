@@ -0,0 +1,159 @@
+//! Format detection and the shared parser trait that lets `commands::book`
+//! and `commands::rag` treat every supported book type the same way.
+pub mod pdf;
+
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::epub;
+
+/// Format-neutral book metadata, produced by whichever `BookParser` handles
+/// the book's container format.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BookMetadata {
+    pub title: String,
+    pub author: String,
+    pub author_sort: String,
+    pub publisher: Option<String>,
+    pub language: Option<String>,
+    pub description: Option<String>,
+    pub subjects: Vec<String>,
+}
+
+/// Format-neutral chapter/page of reading content.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BookChapter {
+    pub index: usize,
+    pub title: String,
+    pub content: String,
+    pub text: String,
+}
+
+impl From<epub::EpubMetadata> for BookMetadata {
+    fn from(m: epub::EpubMetadata) -> Self {
+        BookMetadata {
+            title: m.title,
+            author: m.author,
+            author_sort: m.author_sort,
+            publisher: m.publisher,
+            language: m.language,
+            description: m.description,
+            subjects: m.subjects,
+        }
+    }
+}
+
+impl From<epub::EpubChapter> for BookChapter {
+    fn from(c: epub::EpubChapter) -> Self {
+        BookChapter {
+            index: c.index,
+            title: c.title,
+            content: c.content,
+            text: c.text,
+        }
+    }
+}
+
+/// Anything that can turn a book file on disk into metadata and chapters,
+/// regardless of its underlying container format.
+pub trait BookParser {
+    fn parse_metadata(&self, file_path: &str) -> Result<BookMetadata>;
+    fn extract_chapters(&self, file_path: &str) -> Result<Vec<BookChapter>>;
+}
+
+struct EpubParser;
+
+impl BookParser for EpubParser {
+    fn parse_metadata(&self, file_path: &str) -> Result<BookMetadata> {
+        epub::parse_metadata(file_path).map(Into::into)
+    }
+
+    fn extract_chapters(&self, file_path: &str) -> Result<Vec<BookChapter>> {
+        Ok(epub::extract_chapters(file_path)?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+}
+
+/// Detect a book's format from its file extension, falling back to magic
+/// bytes when the extension is missing or not a recognized one. A `.epub` or
+/// `.pdf` extension is trusted as-is; the file's contents are not checked.
+pub fn detect_format(file_path: &str) -> Result<&'static str> {
+    let ext = Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    match ext.as_str() {
+        "epub" => return Ok("epub"),
+        "pdf" => return Ok("pdf"),
+        _ => {}
+    }
+
+    let mut magic = [0u8; 4];
+    let mut file = std::fs::File::open(file_path)?;
+    let read = file.read(&mut magic)?;
+    let magic = &magic[..read];
+
+    if magic.starts_with(b"%PDF") {
+        Ok("pdf")
+    } else if magic.starts_with(b"PK\x03\x04") {
+        // EPUB is a zip container; a missing/wrong extension still has the
+        // zip local-file-header signature.
+        Ok("epub")
+    } else {
+        Err(anyhow!("unrecognized or unsupported book format: {file_path}"))
+    }
+}
+
+/// Get the parser implementation for a previously detected format.
+pub fn parser_for(format: &str) -> Result<Box<dyn BookParser>> {
+    match format {
+        "epub" => Ok(Box::new(EpubParser)),
+        "pdf" => Ok(Box::new(pdf::PdfParser)),
+        other => Err(anyhow!("unsupported book format: {other}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_temp_file(name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "readany-format-test-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn detect_format_falls_back_to_pdf_magic_bytes() {
+        let path = write_temp_file("no-ext-pdf", b"%PDF-1.4 rest of file");
+        assert_eq!(detect_format(path.to_str().unwrap()).unwrap(), "pdf");
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn detect_format_falls_back_to_epub_magic_bytes() {
+        let path = write_temp_file("no-ext-epub", b"PK\x03\x04 rest of the zip");
+        assert_eq!(detect_format(path.to_str().unwrap()).unwrap(), "epub");
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn detect_format_rejects_unrecognized_content() {
+        let path = write_temp_file("no-ext-unknown", b"not a book at all");
+        assert!(detect_format(path.to_str().unwrap()).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -3,6 +3,7 @@ use tauri::Manager;
 mod commands;
 mod db;
 mod epub;
+mod formats;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -27,6 +28,7 @@ pub fn run() {
             commands::book::get_book,
             commands::book::delete_book,
             commands::book::update_book_progress,
+            commands::book::scan_library,
             commands::rag::vectorize_book,
             commands::rag::search_book,
             commands::rag::get_vectorize_status,